@@ -0,0 +1,304 @@
+//! Verifiable e-voting: prove that an array of El Gamal ciphertexts encodes
+//! a unit vector (exactly one entry is `1`, the rest `0`) without revealing
+//! which index is set, following Catalyst's unit-vector ZKP.
+//!
+//! Each ciphertext's "encrypts 0 or 1" statement is proved with a CDS
+//! OR-composition of two Chaum–Pedersen instances: the real branch runs the
+//! genuine sigma protocol, the false branch is simulated by picking its
+//! response and sub-challenge first and back-solving its commitment, and
+//! the transcript challenge is split so the two sub-challenges sum to it.
+//! A final linear proof — a plain [`LogEqualityProof`] over the
+//! homomorphically aggregated ciphertext — shows the component plaintexts
+//! sum to exactly `1`, which only holds for a true unit vector.
+
+use ark_ff::{UniformRand, Zero};
+use ark_std::{rand::Rng, vec::Vec};
+
+use crate::log_equality::LogEqualityProof;
+use crate::suite::Suite;
+use crate::transcript::Transcript;
+use crate::{Ciphertext, Group, Params, Scalar};
+
+const DOMAIN_SEPARATOR: &[u8] = b"el-gamal-sigma-protocol/ballot-bit/v1";
+
+fn parts<S: Suite>(ct: &Ciphertext<S>) -> (Group<S>, Group<S>) {
+    (ct.c1.into(), ct.c2.into())
+}
+
+/// A CDS OR-proof that a ciphertext `(c1, c2) = (r·g, r·h + m·g)` has
+/// `m ∈ {0, 1}`, without revealing which.
+struct BitProof<S: Suite> {
+    a1_0: Group<S>,
+    a2_0: Group<S>,
+    a1_1: Group<S>,
+    a2_1: Group<S>,
+    e0: Scalar<S>,
+    e1: Scalar<S>,
+    z0: Scalar<S>,
+    z1: Scalar<S>,
+}
+
+impl<S: Suite> BitProof<S> {
+    /// Prove that `(c1, c2)` encrypts `bit` with randomness `r`.
+    fn prove<R: Rng + Sized>(
+        bit: bool,
+        r: Scalar<S>,
+        g: Group<S>,
+        h: Group<S>,
+        c1: Group<S>,
+        c2: Group<S>,
+        mut rng: R,
+    ) -> Self {
+        // branch 0 proves c1 = r·g, c2 = r·h (true iff m = 0)
+        // branch 1 proves c1 = r·g, c2 - g = r·h (true iff m = 1)
+        let target1 = c2 - g;
+
+        if bit {
+            let k = Scalar::<S>::rand(&mut rng);
+            let a1_1 = g * k;
+            let a2_1 = h * k;
+
+            // simulate the false (0) branch: pick its response and
+            // sub-challenge first, then back-solve its commitment
+            let e0 = Scalar::<S>::rand(&mut rng);
+            let z0 = Scalar::<S>::rand(&mut rng);
+            let a1_0 = g * z0 - c1 * e0;
+            let a2_0 = h * z0 - c2 * e0;
+
+            let e = Self::challenge(&g, &h, &c1, &c2, &a1_0, &a2_0, &a1_1, &a2_1);
+            let e1 = e - e0;
+            let z1 = k + e1 * r;
+
+            BitProof {
+                a1_0,
+                a2_0,
+                a1_1,
+                a2_1,
+                e0,
+                e1,
+                z0,
+                z1,
+            }
+        } else {
+            let k = Scalar::<S>::rand(&mut rng);
+            let a1_0 = g * k;
+            let a2_0 = h * k;
+
+            let e1 = Scalar::<S>::rand(&mut rng);
+            let z1 = Scalar::<S>::rand(&mut rng);
+            let a1_1 = g * z1 - c1 * e1;
+            let a2_1 = h * z1 - target1 * e1;
+
+            let e = Self::challenge(&g, &h, &c1, &c2, &a1_0, &a2_0, &a1_1, &a2_1);
+            let e0 = e - e1;
+            let z0 = k + e0 * r;
+
+            BitProof {
+                a1_0,
+                a2_0,
+                a1_1,
+                a2_1,
+                e0,
+                e1,
+                z0,
+                z1,
+            }
+        }
+    }
+
+    fn verify(&self, g: Group<S>, h: Group<S>, c1: Group<S>, c2: Group<S>) -> bool {
+        let e = Self::challenge(
+            &g, &h, &c1, &c2, &self.a1_0, &self.a2_0, &self.a1_1, &self.a2_1,
+        );
+        if self.e0 + self.e1 != e {
+            return false;
+        }
+
+        let target1 = c2 - g;
+
+        g * self.z0 == self.a1_0 + c1 * self.e0
+            && h * self.z0 == self.a2_0 + c2 * self.e0
+            && g * self.z1 == self.a1_1 + c1 * self.e1
+            && h * self.z1 == self.a2_1 + target1 * self.e1
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn challenge(
+        g: &Group<S>,
+        h: &Group<S>,
+        c1: &Group<S>,
+        c2: &Group<S>,
+        a1_0: &Group<S>,
+        a2_0: &Group<S>,
+        a1_1: &Group<S>,
+        a2_1: &Group<S>,
+    ) -> Scalar<S> {
+        let mut transcript = Transcript::new(DOMAIN_SEPARATOR);
+        transcript.append_point(b"g", g);
+        transcript.append_point(b"h", h);
+        transcript.append_point(b"c1", c1);
+        transcript.append_point(b"c2", c2);
+        transcript.append_point(b"a1_0", a1_0);
+        transcript.append_point(b"a2_0", a2_0);
+        transcript.append_point(b"a1_1", a1_1);
+        transcript.append_point(b"a2_1", a2_1);
+        transcript.challenge::<S>()
+    }
+}
+
+/// A proof that a list of ciphertexts encodes a unit vector.
+pub struct UnitVectorProof<S: Suite> {
+    bit_proofs: Vec<BitProof<S>>,
+    sum_proof: LogEqualityProof<S>,
+}
+
+/// Prove that encrypting a unit vector with `1` at `choice_index` out of
+/// `m` slots was done correctly, without revealing `choice_index`.
+///
+/// Returns the `m` ciphertexts, the proof, and the homomorphically
+/// aggregated ciphertext (`Σ E_i`) — tally-ready, since summing many
+/// voters' aggregates and decrypting the sum yields the vote count.
+pub fn prove_ballot<S: Suite, R: Rng + Sized>(
+    choice_index: usize,
+    m: usize,
+    params: &Params<S>,
+    mut rng: R,
+) -> (Vec<Ciphertext<S>>, UnitVectorProof<S>, Ciphertext<S>) {
+    assert!(choice_index < m, "choice index out of range");
+
+    let mut ciphertexts = Vec::with_capacity(m);
+    let mut bit_proofs = Vec::with_capacity(m);
+
+    let mut agg_c1 = Group::<S>::zero();
+    let mut agg_c2 = Group::<S>::zero();
+    let mut agg_r = Scalar::<S>::zero();
+
+    for i in 0..m {
+        let bit = i == choice_index;
+        let m_scalar = if bit {
+            Scalar::<S>::from(1u64)
+        } else {
+            Scalar::<S>::zero()
+        };
+        let r = Scalar::<S>::rand(&mut rng);
+        let c1 = params.g * r;
+        let c2 = params.h * r + params.g * m_scalar;
+
+        bit_proofs.push(BitProof::prove(bit, r, params.g, params.h, c1, c2, &mut rng));
+        ciphertexts.push(Ciphertext {
+            c1: c1.into(),
+            c2: c2.into(),
+        });
+
+        agg_c1 += c1;
+        agg_c2 += c2;
+        agg_r += r;
+    }
+
+    // the aggregate ciphertext sums homomorphically to Enc(Σ m_i) = Enc(1)
+    // for a genuine unit vector, which this proves via log-equality of
+    // (agg_c1 relative to g) and (agg_c2 - g relative to h)
+    let sum_proof = LogEqualityProof::prove(
+        agg_r,
+        params.g,
+        params.h,
+        agg_c1,
+        agg_c2 - params.g,
+        &mut rng,
+    );
+
+    let aggregate = Ciphertext {
+        c1: agg_c1.into(),
+        c2: agg_c2.into(),
+    };
+
+    (
+        ciphertexts,
+        UnitVectorProof {
+            bit_proofs,
+            sum_proof,
+        },
+        aggregate,
+    )
+}
+
+/// Verify a unit-vector proof against its ciphertexts and aggregate.
+pub fn verify_ballot<S: Suite>(
+    ciphertexts: &[Ciphertext<S>],
+    proof: &UnitVectorProof<S>,
+    aggregate: &Ciphertext<S>,
+    params: &Params<S>,
+) -> bool {
+    if ciphertexts.len() != proof.bit_proofs.len() {
+        return false;
+    }
+
+    let mut agg_c1 = Group::<S>::zero();
+    let mut agg_c2 = Group::<S>::zero();
+
+    for (ct, bit_proof) in ciphertexts.iter().zip(proof.bit_proofs.iter()) {
+        let (c1, c2) = parts(ct);
+        if !bit_proof.verify(params.g, params.h, c1, c2) {
+            return false;
+        }
+        agg_c1 += c1;
+        agg_c2 += c2;
+    }
+
+    let (actual_c1, actual_c2) = parts(aggregate);
+    if agg_c1 != actual_c1 || agg_c2 != actual_c2 {
+        return false;
+    }
+
+    proof
+        .sum_proof
+        .verify(params.g, params.h, actual_c1, actual_c2 - params.g)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Shake128JubJub;
+    use ark_ec::Group as ArkGroup;
+    use ark_ed_on_bls12_381::EdwardsProjective as JubJub;
+    use ark_std::{ops::Mul, test_rng};
+
+    type Params = crate::Params<Shake128JubJub>;
+
+    #[test]
+    pub fn prove_and_verify_unit_vector() {
+        let mut rng = test_rng();
+        let g: JubJub = JubJub::generator().into();
+        let x = <JubJub as ArkGroup>::ScalarField::rand(&mut rng);
+        let h: JubJub = g.mul(x).into();
+        let params = Params::new(g, h);
+
+        let (ciphertexts, proof, aggregate) = prove_ballot(2, 4, &params, test_rng());
+        assert_eq!(
+            verify_ballot(&ciphertexts, &proof, &aggregate, &params),
+            true
+        );
+
+        // the aggregate should decrypt to exactly g (i.e. the tally "1")
+        assert_eq!(aggregate.decrypt(x), g);
+    }
+
+    #[test]
+    pub fn verify_fails_if_a_ciphertext_is_tampered_with() {
+        let mut rng = test_rng();
+        let g: JubJub = JubJub::generator().into();
+        let x = <JubJub as ArkGroup>::ScalarField::rand(&mut rng);
+        let h: JubJub = g.mul(x).into();
+        let params = Params::new(g, h);
+
+        let (mut ciphertexts, proof, aggregate) = prove_ballot(0, 3, &params, test_rng());
+        // flip the first ciphertext's c2, breaking both its bit proof and
+        // the aggregate's sum-to-one relation
+        ciphertexts[0].c2 = (Group::<Shake128JubJub>::from(ciphertexts[0].c2) + g).into();
+
+        assert_eq!(
+            verify_ballot(&ciphertexts, &proof, &aggregate, &params),
+            false
+        );
+    }
+}
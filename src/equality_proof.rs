@@ -0,0 +1,189 @@
+//! A proof that a twisted-ElGamal ciphertext and a separate Pedersen
+//! commitment both encode the same message, following the construction
+//! used by Solana's confidential transfer `ciphertext_commitment_equality_proof`.
+//!
+//! A twisted-ElGamal ciphertext separates the usual El Gamal ciphertext
+//! into a Pedersen-style `commitment` (`m·h_msg + r·g`) and a `handle`
+//! (`r·pubkey`) that lets the holder of the secret key behind `pubkey`
+//! recover `r·g` and thus decrypt. Because the commitment half is itself a
+//! Pedersen commitment to `m`, it can be proven equal to an independently
+//! blinded Pedersen commitment to the same `m` without revealing `m`, either
+//! opening, or the secret key — enabling auditable confidential-transfer
+//! style flows where a value is simultaneously committed and encrypted.
+
+use ark_ff::{Field, UniformRand};
+use ark_std::rand::Rng;
+
+use crate::suite::Suite;
+use crate::transcript::Transcript;
+use crate::{Group, Scalar};
+
+const DOMAIN_SEPARATOR: &[u8] = b"el-gamal-sigma-protocol/ciphertext-commitment-equality/v1";
+
+/// A twisted-ElGamal ciphertext: a Pedersen commitment to `m` under bases
+/// `h_msg`/`g`, plus a decryption handle for the holder of the secret key
+/// behind `pubkey`.
+pub struct TwistedCiphertext<S: Suite> {
+    commitment: Group<S>,
+    handle: Group<S>,
+}
+
+impl<S: Suite> TwistedCiphertext<S> {
+    /// Encrypt `m` to `pubkey` under the blinding/handle base `g` and
+    /// message base `h_msg`, returning the ciphertext and the blinding `r`
+    /// used — the caller needs `r` to also form a matching Pedersen
+    /// commitment to `m`.
+    pub fn encrypt<R: Rng + Sized>(
+        m: Scalar<S>,
+        g: Group<S>,
+        h_msg: Group<S>,
+        pubkey: Group<S>,
+        mut rng: R,
+    ) -> (Self, Scalar<S>) {
+        let r = Scalar::<S>::rand(&mut rng);
+        let commitment = h_msg * m + g * r;
+        let handle = pubkey * r;
+        (TwistedCiphertext { commitment, handle }, r)
+    }
+
+    /// Recover `m·h_msg` using the secret key `x` where `pubkey = x·g`.
+    pub fn decrypt(&self, x: Scalar<S>) -> Group<S> {
+        let r_g = self.handle * x.inverse().expect("secret key must be nonzero");
+        self.commitment - r_g
+    }
+}
+
+/// A proof that `ciphertext` and `pedersen_commitment` both commit to the
+/// same message `m`.
+///
+/// The prover must know the ciphertext's opening `r1`, the commitment's
+/// opening `r2`, and `m` itself; decrypting the ciphertext separately
+/// additionally requires the secret key behind its `pubkey`, but that key is
+/// not part of this statement.
+pub struct CiphertextCommitmentEqualityProof<S: Suite> {
+    a_ct: Group<S>,
+    a_comm: Group<S>,
+    z_m: Scalar<S>,
+    z_r1: Scalar<S>,
+    z_r2: Scalar<S>,
+}
+
+impl<S: Suite> CiphertextCommitmentEqualityProof<S> {
+    /// Prove that `ciphertext` (opened with `r1`) and `pedersen_commitment`
+    /// (opened with `r2`) both commit to `m`.
+    pub fn prove<R: Rng + Sized>(
+        m: Scalar<S>,
+        r1: Scalar<S>,
+        r2: Scalar<S>,
+        g: Group<S>,
+        h_msg: Group<S>,
+        ciphertext: &TwistedCiphertext<S>,
+        pedersen_commitment: Group<S>,
+        mut rng: R,
+    ) -> Self {
+        let mu = Scalar::<S>::rand(&mut rng);
+        let rho1 = Scalar::<S>::rand(&mut rng);
+        let rho2 = Scalar::<S>::rand(&mut rng);
+
+        // mask of the ciphertext's own commitment component: mu·h_msg + rho1·g
+        let a_ct = h_msg * mu + g * rho1;
+        // mask of the independent Pedersen commitment: mu·h_msg + rho2·g
+        let a_comm = h_msg * mu + g * rho2;
+
+        let e = Self::challenge(&g, &h_msg, ciphertext, &pedersen_commitment, &a_ct, &a_comm);
+
+        CiphertextCommitmentEqualityProof {
+            a_ct,
+            a_comm,
+            z_m: mu + e * m,
+            z_r1: rho1 + e * r1,
+            z_r2: rho2 + e * r2,
+        }
+    }
+
+    /// Verify the proof against the ciphertext and commitment bases.
+    pub fn verify(
+        &self,
+        g: Group<S>,
+        h_msg: Group<S>,
+        ciphertext: &TwistedCiphertext<S>,
+        pedersen_commitment: Group<S>,
+    ) -> bool {
+        let e = Self::challenge(&g, &h_msg, ciphertext, &pedersen_commitment, &self.a_ct, &self.a_comm);
+
+        let ct_ok = h_msg * self.z_m + g * self.z_r1 == self.a_ct + ciphertext.commitment * e;
+        let comm_ok = h_msg * self.z_m + g * self.z_r2 == self.a_comm + pedersen_commitment * e;
+        ct_ok && comm_ok
+    }
+
+    fn challenge(
+        g: &Group<S>,
+        h_msg: &Group<S>,
+        ciphertext: &TwistedCiphertext<S>,
+        pedersen_commitment: &Group<S>,
+        a_ct: &Group<S>,
+        a_comm: &Group<S>,
+    ) -> Scalar<S> {
+        let mut transcript = Transcript::new(DOMAIN_SEPARATOR);
+        transcript.append_point(b"g", g);
+        transcript.append_point(b"h_msg", h_msg);
+        transcript.append_point(b"ct_commitment", &ciphertext.commitment);
+        transcript.append_point(b"ct_handle", &ciphertext.handle);
+        transcript.append_point(b"pedersen_commitment", pedersen_commitment);
+        transcript.append_point(b"a_ct", a_ct);
+        transcript.append_point(b"a_comm", a_comm);
+        transcript.challenge::<S>()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Shake128JubJub;
+    use ark_ec::Group as ArkGroup;
+    use ark_ed_on_bls12_381::EdwardsProjective as JubJub;
+    use ark_std::{ops::Mul, test_rng};
+
+    type Ciphertext = TwistedCiphertext<Shake128JubJub>;
+    type Proof = CiphertextCommitmentEqualityProof<Shake128JubJub>;
+
+    #[test]
+    pub fn prove_and_verify() {
+        let mut rng = test_rng();
+        let g: JubJub = JubJub::generator().into();
+        let h_msg: JubJub = g.mul(<JubJub as ArkGroup>::ScalarField::rand(&mut rng)).into();
+        let x = <JubJub as ArkGroup>::ScalarField::rand(&mut rng);
+        let pubkey: JubJub = g.mul(x).into();
+
+        let m = <JubJub as ArkGroup>::ScalarField::rand(&mut rng);
+        let (ciphertext, r1) = Ciphertext::encrypt(m, g, h_msg, pubkey, test_rng());
+
+        let r2 = <JubJub as ArkGroup>::ScalarField::rand(&mut rng);
+        let pedersen_commitment: JubJub = (h_msg.mul(m) + g.mul(r2)).into();
+
+        let proof = Proof::prove(m, r1, r2, g, h_msg, &ciphertext, pedersen_commitment, test_rng());
+        assert_eq!(proof.verify(g, h_msg, &ciphertext, pedersen_commitment), true);
+
+        // the ciphertext should also decrypt back to m·h_msg
+        assert_eq!(ciphertext.decrypt(x), h_msg.mul(m).into());
+    }
+
+    #[test]
+    pub fn verify_fails_when_commitment_is_to_a_different_message() {
+        let mut rng = test_rng();
+        let g: JubJub = JubJub::generator().into();
+        let h_msg: JubJub = g.mul(<JubJub as ArkGroup>::ScalarField::rand(&mut rng)).into();
+        let x = <JubJub as ArkGroup>::ScalarField::rand(&mut rng);
+        let pubkey: JubJub = g.mul(x).into();
+
+        let m = <JubJub as ArkGroup>::ScalarField::rand(&mut rng);
+        let (ciphertext, r1) = Ciphertext::encrypt(m, g, h_msg, pubkey, test_rng());
+
+        let other_m = <JubJub as ArkGroup>::ScalarField::rand(&mut rng);
+        let r2 = <JubJub as ArkGroup>::ScalarField::rand(&mut rng);
+        let mismatched_commitment: JubJub = (h_msg.mul(other_m) + g.mul(r2)).into();
+
+        let proof = Proof::prove(m, r1, r2, g, h_msg, &ciphertext, mismatched_commitment, test_rng());
+        assert_eq!(proof.verify(g, h_msg, &ciphertext, mismatched_commitment), false);
+    }
+}
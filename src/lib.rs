@@ -1,20 +1,40 @@
 #![no_std]
-use ark_ec::CurveGroup;
-use ark_ff::{fields::PrimeField, UniformRand};
+use ark_ec::{CurveGroup, VariableBaseMSM};
+use ark_ff::{UniformRand, Zero};
 use ark_serialize::{CanonicalSerialize, SerializationError};
 use ark_std::{marker::PhantomData, rand::Rng, vec::Vec};
-use sha3::{
-    digest::{ExtendableOutput, Update, XofReader},
-    Shake128,
-};
 
-// a public commitment for a point in the curbe group's scalar field
-pub type Commitment<C> = C;
+mod ballot;
+mod equality_proof;
+mod log_equality;
+mod pedersen;
+mod suite;
+mod transcript;
+
+pub use ballot::{prove_ballot, verify_ballot, UnitVectorProof};
+pub use equality_proof::{CiphertextCommitmentEqualityProof, TwistedCiphertext};
+pub use log_equality::LogEqualityProof;
+pub use pedersen::{hash_to_curve, PedersenCommitment, PedersenOpening, PedersenOpeningProof};
+pub use suite::{Shake128JubJub, Suite};
+use transcript::Transcript;
+
+/// domain separator absorbed as the first element of every proof transcript,
+/// so this protocol's challenges can never collide with another protocol's
+const DOMAIN_SEPARATOR: &[u8] = b"el-gamal-sigma-protocol/v1";
+
+/// domain separator for deriving this protocol's default Pedersen blinding
+/// generator via [`hash_to_curve`]
+const PEDERSEN_BLINDING_GENERATOR_DOMAIN: &[u8] = b"el-gamal-sigma-protocol/pedersen-blinding-h/v1";
+
+/// shorthand for the group a ciphersuite operates in
+type Group<S> = <S as Suite>::Group;
+/// shorthand for that group's scalar field
+type Scalar<S> = <Group<S> as CurveGroup>::ScalarField;
 
 // represents an el gamal ciphertext
-pub struct Ciphertext<C: CurveGroup> {
-    c1: C::Affine,
-    c2: C::Affine,
+pub struct Ciphertext<S: Suite> {
+    c1: <Group<S> as CurveGroup>::Affine,
+    c2: <Group<S> as CurveGroup>::Affine,
 }
 
 #[derive(Debug)]
@@ -22,7 +42,7 @@ pub enum Error {
     SerializationError,
 }
 
-impl<C: CurveGroup> Ciphertext<C> {
+impl<S: Suite> Ciphertext<S> {
     fn serialize_compressed(&self) -> Result<(Vec<u8>, Vec<u8>), SerializationError> {
         let mut c1_bytes = Vec::new();
         let mut c2_bytes = Vec::new();
@@ -32,125 +52,241 @@ impl<C: CurveGroup> Ciphertext<C> {
 
         Ok((c1_bytes, c2_bytes))
     }
+
+    /// Decrypt with secret key `x` (where the ciphertext's public key is
+    /// `h = g^x`), recovering `g^s` rather than `s` itself — exponential El
+    /// Gamal only ever yields the message's image under the basepoint.
+    pub fn decrypt(&self, secret_key: Scalar<S>) -> Group<S> {
+        let c1: Group<S> = self.c1.into();
+        let c2: Group<S> = self.c2.into();
+        c2 - c1 * secret_key
+    }
+
+    /// Decrypt and recover `s` itself by brute-force discrete log search
+    /// against `basepoint`, for message spaces small enough to search
+    /// (e.g. vote tallies or small balances). Returns `None` if no `s` in
+    /// `0..max` decrypts to the recovered point.
+    pub fn decrypt_to_u64(&self, secret_key: Scalar<S>, basepoint: Group<S>, max: u64) -> Option<u64> {
+        let point = self.decrypt(secret_key);
+        let mut acc = Group::<S>::zero();
+        for s in 0..max {
+            if acc == point {
+                return Some(s);
+            }
+            acc += basepoint;
+        }
+        None
+    }
+}
+
+/// A precomputed table mapping `s·basepoint -> s` for `s` in `0..size`, so
+/// repeated decryptions over the same small message space don't each redo
+/// the brute-force search.
+pub struct DiscreteLogTable<S: Suite> {
+    entries: Vec<(Group<S>, u64)>,
+}
+
+impl<S: Suite> DiscreteLogTable<S> {
+    pub fn build(basepoint: Group<S>, size: u64) -> Self {
+        let mut entries = Vec::with_capacity(size as usize);
+        let mut acc = Group::<S>::zero();
+        for s in 0..size {
+            entries.push((acc, s));
+            acc += basepoint;
+        }
+        DiscreteLogTable { entries }
+    }
+
+    pub fn lookup(&self, point: Group<S>) -> Option<u64> {
+        self.entries
+            .iter()
+            .find(|(p, _)| *p == point)
+            .map(|(_, s)| *s)
+    }
 }
 
-/// the NIZK PoK
-pub struct PoK<C: CurveGroup> {
-    /// the commitment to the random value (e.g. rG)
-    pub t: C,
-    /// the 'blinding' commitment to the random value (e.g. rH)
-    pub a: C,
-    /// the challenge (e.g. z = k + es)
-    pub z: C::ScalarField,
+/// the NIZK PoK of a Pedersen commitment's opening `(s, blinding)`
+pub struct PoK<S: Suite> {
+    /// the commitment to the random value used to mask `s` (e.g. `k_s·g`)
+    pub t: Group<S>,
+    /// the commitment to the random value used to mask `blinding` (e.g. `k_b·pedersen_h`)
+    pub a: Group<S>,
+    /// the response for `s` (e.g. `z_s = k_s + e·s`)
+    pub z_s: Scalar<S>,
+    /// the response for `blinding` (e.g. `z_b = k_b + e·blinding`)
+    pub z_b: Scalar<S>,
 }
 
-/// public parameters for El Gamal encryption
+/// public parameters for El Gamal encryption and the Pedersen commitment
+/// bound to it
 #[derive(Clone, Debug)]
-pub struct Params<C: CurveGroup> {
-    pub g: C,
-    pub h: C,
+pub struct Params<S: Suite> {
+    pub g: Group<S>,
+    pub h: Group<S>,
+    /// independent Pedersen blinding generator, with unknown discrete log
+    /// relative to `g`; see [`hash_to_curve`]. Private so it can only ever
+    /// be the output of that derivation — a hand-constructed point here
+    /// (e.g. one equal to `g`, or with any other known relation to it)
+    /// silently destroys the hiding/binding property of every commitment
+    /// made under these params.
+    pedersen_h: Group<S>,
 }
 
-pub struct ElGamalSigmaProtocol<C> {
-    _c: PhantomData<C>,
+impl<S: Suite> Params<S> {
+    /// Build params from `g`/`h`, deriving the Pedersen blinding generator
+    /// deterministically so callers don't need to manage it themselves.
+    pub fn new(g: Group<S>, h: Group<S>) -> Self {
+        Params {
+            g,
+            h,
+            pedersen_h: hash_to_curve::<S>(PEDERSEN_BLINDING_GENERATOR_DOMAIN),
+        }
+    }
+
+    /// The independent Pedersen blinding generator derived in [`Params::new`].
+    pub fn pedersen_h(&self) -> Group<S> {
+        self.pedersen_h
+    }
+}
+
+pub struct ElGamalSigmaProtocol<S: Suite> {
+    _s: PhantomData<S>,
 }
 
-impl<C: CurveGroup> ElGamalSigmaProtocol<C> {
+impl<S: Suite> ElGamalSigmaProtocol<S> {
     /// Prove that a commitment is of the preimage of an El Gamal ciphertext
     /// without revealing the message
     ///
     pub fn prove<R: Rng + Sized>(
-        s: C::ScalarField,
-        params: Params<C>,
+        s: Scalar<S>,
+        params: Params<S>,
         mut rng: R,
-    ) -> (Commitment<C>, Ciphertext<C>, PoK<C>) {
-        // el gamal encryption
-        let r = C::ScalarField::rand(&mut rng);
+    ) -> (PedersenCommitment<S>, Ciphertext<S>, PoK<S>) {
+        // exponential el gamal encryption of s under public key h = g^x:
+        // c1 = g^r, c2 = h^r * g^s, which decrypts to g^s given x (see
+        // `Ciphertext::decrypt`)
+        let r = Scalar::<S>::rand(&mut rng);
         let c1 = params.g * r;
-        let c2 = params.h * (s * r);
+        let c2 = params.h * r + params.g * s;
 
-        let ct: Ciphertext<C> = Ciphertext {
+        let ct: Ciphertext<S> = Ciphertext {
             c1: c1.into(),
             c2: c2.into(),
         };
 
-        // the commitment
-        let c: Commitment<C> = params.g * s + params.h * s;
+        // a binding-and-hiding Pedersen commitment to s, blinded
+        // independently of the ciphertext's own randomness
+        let blinding = Scalar::<S>::rand(&mut rng);
+        let c = PedersenCommitment::commit(params.g, params.pedersen_h(), s, blinding);
 
-        let k = C::ScalarField::rand(&mut rng);
-        let t = params.g * k;
-        let a = params.h * k;
+        let k_s = Scalar::<S>::rand(&mut rng);
+        let k_b = Scalar::<S>::rand(&mut rng);
+        let t = params.g * k_s;
+        let a = params.pedersen_h() * k_b;
 
-        let mut t_bytes = Vec::new();
-        let mut a_bytes = Vec::new();
-        t.serialize_compressed(&mut t_bytes)
-            .expect("group element should exist");
-        a.serialize_compressed(&mut a_bytes)
-            .expect("group element should exist");
-
-        let mut inputs = Vec::new();
-        inputs.push(t_bytes);
-        inputs.push(a_bytes);
-        let (c1_bytes, c2_bytes) = ct
-            .serialize_compressed()
-            .expect("group elements should exist");
-        inputs.push(c1_bytes);
-        inputs.push(c2_bytes);
-
-        let challenge: C::ScalarField =
-            C::ScalarField::from_be_bytes_mod_order(&shake128(inputs.as_ref()));
-        let z = k + challenge * s;
-        (c, ct, PoK { t, a, z })
+        let challenge = Self::challenge(&params, &c, &ct, &t, &a);
+        let z_s = k_s + challenge * s;
+        let z_b = k_b + challenge * blinding;
+        (c, ct, PoK { t, a, z_s, z_b })
     }
 
     /// verify a proof that a commitment is of the preimage of an el gamal ciphertext
     pub fn verify(
-        commitment: Commitment<C>,
-        ciphertext: Ciphertext<C>,
-        proof: PoK<C>,
-        params: Params<C>,
+        commitment: PedersenCommitment<S>,
+        ciphertext: Ciphertext<S>,
+        proof: PoK<S>,
+        params: Params<S>,
     ) -> bool {
-        let mut t_bytes = Vec::new();
-        let mut a_bytes = Vec::new();
-        proof
-            .t
-            .serialize_compressed(&mut t_bytes)
-            .expect("group element should exist");
-        proof
-            .a
-            .serialize_compressed(&mut a_bytes)
-            .expect("group element should exist");
-
-        let mut inputs = Vec::new();
-        inputs.push(t_bytes);
-        inputs.push(a_bytes);
-        let (c1_bytes, c2_bytes) = ciphertext
-            .serialize_compressed()
-            .expect("group element should exist");
-        inputs.push(c1_bytes);
-        inputs.push(c2_bytes);
-
-        let challenge: C::ScalarField =
-            C::ScalarField::from_be_bytes_mod_order(&shake128(inputs.as_ref()));
+        let challenge = Self::challenge(&params, &commitment, &ciphertext, &proof.t, &proof.a);
 
-        let zg = params.g * proof.z;
-        let zh = params.h * proof.z;
+        let lhs = params.g * proof.z_s + params.pedersen_h() * proof.z_b;
 
-        zg + zh == proof.t + proof.a + commitment * challenge
+        lhs == proof.t + proof.a + *commitment.as_point() * challenge
     }
-}
 
-fn shake128(input: &[Vec<u8>]) -> [u8; 32] {
-    let mut h = Shake128::default();
-
-    for item in input.iter() {
-        h.update(item);
+    /// Build the proof transcript and squeeze out the Fiat–Shamir challenge.
+    ///
+    /// Every public value of the statement is absorbed, in order: the
+    /// protocol domain separator, the public parameters `g`/`h`, the
+    /// commitment, the ciphertext, and finally the prover's first message
+    /// `t`/`a`. Binding `g`, `h` and the commitment (not just `t`, `a`, `c1`,
+    /// `c2`) prevents a proof from being transplanted across statements that
+    /// differ only in those values. The actual hash used to squeeze the
+    /// challenge is whatever suite `S` specifies.
+    fn challenge(
+        params: &Params<S>,
+        commitment: &PedersenCommitment<S>,
+        ciphertext: &Ciphertext<S>,
+        t: &Group<S>,
+        a: &Group<S>,
+    ) -> Scalar<S> {
+        let mut transcript = Transcript::new(DOMAIN_SEPARATOR);
+        transcript.append_point(b"g", &params.g);
+        transcript.append_point(b"h", &params.h);
+        transcript.append_point(b"commitment", commitment.as_point());
+        let (c1_bytes, c2_bytes) = ciphertext
+            .serialize_compressed()
+            .expect("group elements should exist");
+        transcript.append(b"c1", &c1_bytes);
+        transcript.append(b"c2", &c2_bytes);
+        transcript.append_point(b"t", t);
+        transcript.append_point(b"a", a);
+        transcript.challenge::<S>()
     }
 
-    let mut o = [0u8; 32];
-    // get challenge from hasher
-    h.finalize_xof().read(&mut o);
-    o
+    /// Verify a batch of proofs against shared parameters in a single pass.
+    ///
+    /// Each proof `i` satisfies `z_s_i·g + z_b_i·pedersen_h = t_i + a_i +
+    /// e_i·c_i`. Checking that individually for every proof is sound but
+    /// costs N independent verifications. Instead, weight each proof's
+    /// equation by an independent random nonzero scalar `ρ_i` and sum them:
+    ///
+    /// `(Σ ρ_i·z_s_i)·g + (Σ ρ_i·z_b_i)·pedersen_h = Σ ρ_i·t_i + Σ ρ_i·a_i + Σ (ρ_i·e_i)·c_i`
+    ///
+    /// A forged proof only survives this combined check with probability
+    /// `1/|F|` over the verifier's choice of `ρ_i`, and the right-hand side
+    /// is computed as a single multiscalar multiplication over the collected
+    /// points `{t_i, a_i, c_i}` — one batched Pippenger pass instead of `3N`
+    /// independent scalar multiplications — so verifying a batch is far
+    /// cheaper than calling `verify` N times.
+    pub fn verify_batch<R: Rng>(
+        items: &[(PedersenCommitment<S>, Ciphertext<S>, PoK<S>)],
+        params: &Params<S>,
+        mut rng: R,
+    ) -> bool {
+        if items.is_empty() {
+            return true;
+        }
+
+        let mut rho_zs = Scalar::<S>::zero();
+        let mut rho_zb = Scalar::<S>::zero();
+        let mut bases = Vec::with_capacity(3 * items.len());
+        let mut scalars = Vec::with_capacity(3 * items.len());
+
+        for (commitment, ciphertext, proof) in items {
+            let e = Self::challenge(params, commitment, ciphertext, &proof.t, &proof.a);
+
+            let mut rho = Scalar::<S>::rand(&mut rng);
+            while rho.is_zero() {
+                rho = Scalar::<S>::rand(&mut rng);
+            }
+
+            rho_zs += rho * proof.z_s;
+            rho_zb += rho * proof.z_b;
+
+            bases.push(proof.t.into_affine());
+            scalars.push(rho);
+            bases.push(proof.a.into_affine());
+            scalars.push(rho);
+            bases.push((*commitment.as_point()).into_affine());
+            scalars.push(rho * e);
+        }
+
+        let rhs = Group::<S>::msm(&bases, &scalars)
+            .expect("bases and scalars are collected pairwise, so their lengths match");
+
+        params.g * rho_zs + params.pedersen_h() * rho_zb == rhs
+    }
 }
 
 #[cfg(test)]
@@ -161,6 +297,8 @@ mod test {
     use ark_ed_on_bls12_381::EdwardsProjective as JubJub;
     use ark_std::{ops::Mul, test_rng};
 
+    type Protocol = ElGamalSigmaProtocol<Shake128JubJub>;
+
     #[test]
     pub fn prove_and_verify() {
         let mut rng = test_rng();
@@ -170,11 +308,10 @@ mod test {
         // the public key
         let h: JubJub = g.mul(x).into();
 
-        let params = Params { g, h };
+        let params = Params::new(g, h);
 
-        let (commitment, ciphertext, proof) =
-            ElGamalSigmaProtocol::prove(x, params.clone(), test_rng());
-        let result = ElGamalSigmaProtocol::verify(commitment, ciphertext, proof, params);
+        let (commitment, ciphertext, proof) = Protocol::prove(x, params.clone(), test_rng());
+        let result = Protocol::verify(commitment, ciphertext, proof, params);
         assert_eq!(result, true);
     }
 
@@ -191,14 +328,14 @@ mod test {
         let bad_proof = PoK {
             t: g.mul(j).into(),
             a: g.mul(j).into(),
-            z: j,
+            z_s: j,
+            z_b: j,
         };
 
-        let params = Params { g, h };
+        let params = Params::new(g, h);
 
-        let (commitment, ciphertext, _proof) =
-            ElGamalSigmaProtocol::prove(x, params.clone(), test_rng());
-        let result = ElGamalSigmaProtocol::verify(commitment, ciphertext, bad_proof, params);
+        let (commitment, ciphertext, _proof) = Protocol::prove(x, params.clone(), test_rng());
+        let result = Protocol::verify(commitment, ciphertext, bad_proof, params);
         assert_eq!(result, false);
     }
 
@@ -212,13 +349,102 @@ mod test {
         let h: JubJub = g.mul(x).into();
 
         let j = <JubJub as Group>::ScalarField::rand(&mut rng);
-        let bad_commitment = g.mul(j).into();
+        let bad_commitment = PedersenCommitment::commit(g, g, j, j);
 
-        let params = Params { g, h };
+        let params = Params::new(g, h);
 
-        let (_commitment, ciphertext, proof) =
-            ElGamalSigmaProtocol::prove(x, params.clone(), test_rng());
-        let result = ElGamalSigmaProtocol::verify(bad_commitment, ciphertext, proof, params);
+        let (_commitment, ciphertext, proof) = Protocol::prove(x, params.clone(), test_rng());
+        let result = Protocol::verify(bad_commitment, ciphertext, proof, params);
         assert_eq!(result, false);
     }
+
+    #[test]
+    pub fn verify_fails_with_mismatched_params() {
+        let mut rng = test_rng();
+        // the secret key
+        let x = <JubJub as Group>::ScalarField::rand(&mut rng);
+        let g: JubJub = JubJub::generator().into();
+        // the public key
+        let h: JubJub = g.mul(x).into();
+
+        let params = Params::new(g, h);
+
+        let (commitment, ciphertext, proof) = Protocol::prove(x, params.clone(), test_rng());
+
+        // swapping g and h after the proof was generated must invalidate it,
+        // since both are now bound into the challenge transcript
+        let swapped_params = Params::new(h, g);
+        let result = Protocol::verify(commitment, ciphertext, proof, swapped_params);
+        assert_eq!(result, false);
+    }
+
+    #[test]
+    pub fn verify_batch_accepts_all_valid_proofs() {
+        let mut rng = test_rng();
+        let g: JubJub = JubJub::generator().into();
+        let secret = <JubJub as Group>::ScalarField::rand(&mut rng);
+        let h: JubJub = g.mul(secret).into();
+        let params = Params::new(g, h);
+
+        let items: Vec<_> = (0..5)
+            .map(|_| {
+                let x = <JubJub as Group>::ScalarField::rand(&mut rng);
+                Protocol::prove(x, params.clone(), test_rng())
+            })
+            .collect();
+
+        assert_eq!(Protocol::verify_batch(&items, &params, test_rng()), true);
+    }
+
+    #[test]
+    pub fn verify_batch_rejects_if_any_proof_is_forged() {
+        let mut rng = test_rng();
+        let g: JubJub = JubJub::generator().into();
+        let secret = <JubJub as Group>::ScalarField::rand(&mut rng);
+        let h: JubJub = g.mul(secret).into();
+        let params = Params::new(g, h);
+
+        let mut items: Vec<_> = (0..5)
+            .map(|_| {
+                let x = <JubJub as Group>::ScalarField::rand(&mut rng);
+                Protocol::prove(x, params.clone(), test_rng())
+            })
+            .collect();
+
+        // corrupt a single proof in the batch
+        items[2].2.z_s += <JubJub as Group>::ScalarField::rand(&mut rng);
+
+        assert_eq!(Protocol::verify_batch(&items, &params, test_rng()), false);
+    }
+
+    #[test]
+    pub fn decrypt_recovers_s_times_basepoint() {
+        let mut rng = test_rng();
+        let x = <JubJub as Group>::ScalarField::rand(&mut rng);
+        let g: JubJub = JubJub::generator().into();
+        let h: JubJub = g.mul(x).into();
+        let params = Params::new(g, h);
+
+        let s = <JubJub as Group>::ScalarField::rand(&mut rng);
+        let (_commitment, ciphertext, _proof) = Protocol::prove(s, params.clone(), test_rng());
+
+        assert_eq!(ciphertext.decrypt(x), g.mul(s).into());
+    }
+
+    #[test]
+    pub fn decrypt_to_u64_recovers_small_values() {
+        let mut rng = test_rng();
+        let x = <JubJub as Group>::ScalarField::rand(&mut rng);
+        let g: JubJub = JubJub::generator().into();
+        let h: JubJub = g.mul(x).into();
+        let params = Params::new(g, h);
+
+        let s = <JubJub as Group>::ScalarField::from(7u64);
+        let (_commitment, ciphertext, _proof) = Protocol::prove(s, params.clone(), test_rng());
+
+        assert_eq!(ciphertext.decrypt_to_u64(x, g, 16), Some(7));
+
+        let table = DiscreteLogTable::<Shake128JubJub>::build(g, 16);
+        assert_eq!(table.lookup(ciphertext.decrypt(x)), Some(7));
+    }
 }
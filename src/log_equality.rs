@@ -0,0 +1,115 @@
+//! A standalone Chaum–Pedersen proof of equality of discrete logs.
+//!
+//! Given two bases `G`, `K` and two group elements `R = r·G`, `B = r·K`,
+//! this proves knowledge of `r` without revealing it. It underlies
+//! correct-decryption and key-consistency proofs. It is independent of
+//! [`crate::ElGamalSigmaProtocol`], whose proof shows knowledge of a
+//! Pedersen commitment's opening rather than an equality of discrete logs.
+
+use ark_ff::UniformRand;
+use ark_std::rand::Rng;
+
+use crate::suite::Suite;
+use crate::transcript::Transcript;
+use crate::{Group, Scalar};
+
+const DOMAIN_SEPARATOR: &[u8] = b"el-gamal-sigma-protocol/log-equality/v1";
+
+/// A proof that `R` and `B` share the same discrete log relative to bases
+/// `G` and `K` respectively.
+pub struct LogEqualityProof<S: Suite> {
+    /// the prover's commitment `x·G`
+    pub x_g: Group<S>,
+    /// the prover's commitment `x·K`
+    pub x_k: Group<S>,
+    /// the response `s = x + e·r`
+    pub s: Scalar<S>,
+}
+
+impl<S: Suite> LogEqualityProof<S> {
+    /// Prove that `big_r = r·g` and `big_b = r·k` for the same `r`.
+    pub fn prove<R: Rng + Sized>(
+        r: Scalar<S>,
+        g: Group<S>,
+        k: Group<S>,
+        big_r: Group<S>,
+        big_b: Group<S>,
+        mut rng: R,
+    ) -> Self {
+        let x = Scalar::<S>::rand(&mut rng);
+        let x_g = g * x;
+        let x_k = k * x;
+
+        let e = Self::challenge(&g, &k, &big_r, &big_b, &x_g, &x_k);
+        let s = x + e * r;
+
+        LogEqualityProof { x_g, x_k, s }
+    }
+
+    /// Verify that `big_r` and `big_b` share a discrete log in bases `g`
+    /// and `k`.
+    pub fn verify(&self, g: Group<S>, k: Group<S>, big_r: Group<S>, big_b: Group<S>) -> bool {
+        let e = Self::challenge(&g, &k, &big_r, &big_b, &self.x_g, &self.x_k);
+
+        g * self.s == self.x_g + big_r * e && k * self.s == self.x_k + big_b * e
+    }
+
+    fn challenge(
+        g: &Group<S>,
+        k: &Group<S>,
+        big_r: &Group<S>,
+        big_b: &Group<S>,
+        x_g: &Group<S>,
+        x_k: &Group<S>,
+    ) -> Scalar<S> {
+        let mut transcript = Transcript::new(DOMAIN_SEPARATOR);
+        transcript.append_point(b"g", g);
+        transcript.append_point(b"k", k);
+        transcript.append_point(b"r", big_r);
+        transcript.append_point(b"b", big_b);
+        transcript.append_point(b"x_g", x_g);
+        transcript.append_point(b"x_k", x_k);
+        transcript.challenge::<S>()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Shake128JubJub;
+    use ark_ec::Group as ArkGroup;
+    use ark_ed_on_bls12_381::EdwardsProjective as JubJub;
+    use ark_std::{ops::Mul, test_rng};
+
+    type Proof = LogEqualityProof<Shake128JubJub>;
+
+    #[test]
+    pub fn prove_and_verify() {
+        let mut rng = test_rng();
+        let g: JubJub = JubJub::generator().into();
+        let k: JubJub = g.mul(<JubJub as ArkGroup>::ScalarField::rand(&mut rng)).into();
+
+        let r = <JubJub as ArkGroup>::ScalarField::rand(&mut rng);
+        let big_r = g.mul(r).into();
+        let big_b = k.mul(r).into();
+
+        let proof = Proof::prove(r, g, k, big_r, big_b, test_rng());
+        assert_eq!(proof.verify(g, k, big_r, big_b), true);
+    }
+
+    #[test]
+    pub fn verify_fails_when_discrete_logs_differ() {
+        let mut rng = test_rng();
+        let g: JubJub = JubJub::generator().into();
+        let k: JubJub = g.mul(<JubJub as ArkGroup>::ScalarField::rand(&mut rng)).into();
+
+        let r = <JubJub as ArkGroup>::ScalarField::rand(&mut rng);
+        let other_r = <JubJub as ArkGroup>::ScalarField::rand(&mut rng);
+        let big_r = g.mul(r).into();
+        // B uses a different exponent than R, so they no longer share a log
+        let big_b = k.mul(other_r).into();
+
+        let proof = Proof::prove(r, g, k, big_r, big_b, test_rng());
+        assert_eq!(proof.verify(g, k, big_r, big_b), false);
+    }
+}
@@ -0,0 +1,240 @@
+//! Binding-and-hiding Pedersen commitments.
+//!
+//! A Pedersen commitment `commit(value, blinding) = value·g + blinding·h`
+//! is binding (the committer can't later open it to a different value) and
+//! hiding (the commitment reveals nothing about `value` on its own) as long
+//! as `h`'s discrete log relative to `g` is unknown to everyone. That's
+//! what [`hash_to_curve`] is for: it derives `h` deterministically from a
+//! domain-separation string via try-and-increment, so nobody (including the
+//! crate) ever learns a scalar `x` with `h = x·g`.
+
+use ark_ec::CurveGroup;
+use ark_ff::{UniformRand, Zero};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::{
+    ops::{Add, Sub},
+    rand::Rng,
+};
+
+use crate::suite::Suite;
+use crate::transcript::Transcript;
+use crate::{Group, Scalar};
+
+const DOMAIN_SEPARATOR: &[u8] = b"el-gamal-sigma-protocol/pedersen-opening/v1";
+
+/// Deterministically derive a group element with unknown discrete log
+/// relative to the generator, by hashing `domain` together with an
+/// incrementing counter until the digest deserializes as a valid point
+/// (`deserialize_compressed` already checks on-curve and correct-subgroup
+/// membership, so the first success is immediately usable).
+///
+/// The digest is sized to the affine point's own compressed encoding (the
+/// curve's *base* field), not routed through [`Suite::hash_to_scalar`] (the
+/// *scalar* field) — those two fields only happen to be the same width for
+/// an embedded curve like JubJub-over-BLS12-381, and for any other group
+/// (e.g. a pairing curve's `G1`/`G2`) a scalar-sized digest would never
+/// deserialize, spinning the loop forever.
+pub fn hash_to_curve<S: Suite>(domain: &'static [u8]) -> Group<S> {
+    let point_len = Group::<S>::zero().into_affine().compressed_size();
+    let mut counter: u64 = 0;
+    loop {
+        let bytes = S::hash_to_bytes(&[domain, &counter.to_be_bytes()], point_len);
+        if let Ok(candidate) =
+            <Group<S> as CurveGroup>::Affine::deserialize_compressed(bytes.as_slice())
+        {
+            return candidate.into();
+        }
+        counter += 1;
+    }
+}
+
+/// A Pedersen commitment `value·g + blinding·h`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PedersenCommitment<S: Suite>(Group<S>);
+
+impl<S: Suite> PedersenCommitment<S> {
+    /// Commit to `value` with the given `blinding`, under bases `g` and `h`.
+    pub fn commit(g: Group<S>, h: Group<S>, value: Scalar<S>, blinding: Scalar<S>) -> Self {
+        PedersenCommitment(g * value + h * blinding)
+    }
+
+    /// The underlying group element.
+    pub fn as_point(&self) -> &Group<S> {
+        &self.0
+    }
+}
+
+impl<S: Suite> Add for PedersenCommitment<S> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        PedersenCommitment(self.0 + rhs.0)
+    }
+}
+
+impl<S: Suite> Sub for PedersenCommitment<S> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        PedersenCommitment(self.0 - rhs.0)
+    }
+}
+
+/// The opening of a [`PedersenCommitment`]: the value and blinding factor
+/// used to produce it. Openings add/subtract componentwise, matching the
+/// homomorphism on the commitments themselves: opening `o1 + o2` opens
+/// `commitment(o1) + commitment(o2)`.
+#[derive(Clone, Copy, Debug)]
+pub struct PedersenOpening<S: Suite> {
+    pub value: Scalar<S>,
+    pub blinding: Scalar<S>,
+}
+
+impl<S: Suite> PedersenOpening<S> {
+    pub fn new(value: Scalar<S>, blinding: Scalar<S>) -> Self {
+        PedersenOpening { value, blinding }
+    }
+
+    /// Commit to this opening under bases `g` and `h`.
+    pub fn commit(&self, g: Group<S>, h: Group<S>) -> PedersenCommitment<S> {
+        PedersenCommitment::commit(g, h, self.value, self.blinding)
+    }
+}
+
+impl<S: Suite> Add for PedersenOpening<S> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        PedersenOpening {
+            value: self.value + rhs.value,
+            blinding: self.blinding + rhs.blinding,
+        }
+    }
+}
+
+impl<S: Suite> Sub for PedersenOpening<S> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        PedersenOpening {
+            value: self.value - rhs.value,
+            blinding: self.blinding - rhs.blinding,
+        }
+    }
+}
+
+/// A standalone proof of knowledge of a [`PedersenCommitment`]'s opening.
+pub struct PedersenOpeningProof<S: Suite> {
+    t: Group<S>,
+    z_value: Scalar<S>,
+    z_blinding: Scalar<S>,
+}
+
+impl<S: Suite> PedersenOpeningProof<S> {
+    /// Prove knowledge of `opening` for `commitment`, under bases `g`, `h`.
+    pub fn prove<R: Rng + Sized>(
+        opening: &PedersenOpening<S>,
+        g: Group<S>,
+        h: Group<S>,
+        commitment: &PedersenCommitment<S>,
+        mut rng: R,
+    ) -> Self {
+        let k_value = Scalar::<S>::rand(&mut rng);
+        let k_blinding = Scalar::<S>::rand(&mut rng);
+        let t = g * k_value + h * k_blinding;
+
+        let e = Self::challenge(&g, &h, commitment, &t);
+        PedersenOpeningProof {
+            t,
+            z_value: k_value + e * opening.value,
+            z_blinding: k_blinding + e * opening.blinding,
+        }
+    }
+
+    /// Verify the proof against `commitment` and bases `g`, `h`.
+    pub fn verify(&self, g: Group<S>, h: Group<S>, commitment: &PedersenCommitment<S>) -> bool {
+        let e = Self::challenge(&g, &h, commitment, &self.t);
+        g * self.z_value + h * self.z_blinding == self.t + *commitment.as_point() * e
+    }
+
+    fn challenge(
+        g: &Group<S>,
+        h: &Group<S>,
+        commitment: &PedersenCommitment<S>,
+        t: &Group<S>,
+    ) -> Scalar<S> {
+        let mut transcript = Transcript::new(DOMAIN_SEPARATOR);
+        transcript.append_point(b"g", g);
+        transcript.append_point(b"h", h);
+        transcript.append_point(b"commitment", commitment.as_point());
+        transcript.append_point(b"t", t);
+        transcript.challenge::<S>()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Shake128JubJub;
+    use ark_ec::Group as ArkGroup;
+    use ark_ed_on_bls12_381::EdwardsProjective as JubJub;
+    use ark_std::test_rng;
+
+    #[test]
+    pub fn hash_to_curve_is_independent_of_the_generator() {
+        let g: JubJub = JubJub::generator().into();
+        let h = hash_to_curve::<Shake128JubJub>(b"test-pedersen-h");
+
+        assert_ne!(g, h);
+        // deterministic: the same domain always derives the same point
+        assert_eq!(h, hash_to_curve::<Shake128JubJub>(b"test-pedersen-h"));
+        // a different domain derives a different point
+        assert_ne!(h, hash_to_curve::<Shake128JubJub>(b"other-pedersen-h"));
+    }
+
+    #[test]
+    pub fn commitments_are_homomorphic() {
+        let mut rng = test_rng();
+        let g: JubJub = JubJub::generator().into();
+        let h = hash_to_curve::<Shake128JubJub>(b"test-pedersen-h");
+
+        let o1 = PedersenOpening::<Shake128JubJub>::new(
+            <JubJub as ArkGroup>::ScalarField::rand(&mut rng),
+            <JubJub as ArkGroup>::ScalarField::rand(&mut rng),
+        );
+        let o2 = PedersenOpening::<Shake128JubJub>::new(
+            <JubJub as ArkGroup>::ScalarField::rand(&mut rng),
+            <JubJub as ArkGroup>::ScalarField::rand(&mut rng),
+        );
+
+        let c1 = o1.commit(g, h);
+        let c2 = o2.commit(g, h);
+        let combined_opening = o1 + o2;
+
+        assert_eq!(c1 + c2, combined_opening.commit(g, h));
+    }
+
+    #[test]
+    pub fn opening_proof_round_trips() {
+        let mut rng = test_rng();
+        let g: JubJub = JubJub::generator().into();
+        let h = hash_to_curve::<Shake128JubJub>(b"test-pedersen-h");
+
+        let opening = PedersenOpening::<Shake128JubJub>::new(
+            <JubJub as ArkGroup>::ScalarField::rand(&mut rng),
+            <JubJub as ArkGroup>::ScalarField::rand(&mut rng),
+        );
+        let commitment = opening.commit(g, h);
+
+        let proof = PedersenOpeningProof::prove(&opening, g, h, &commitment, test_rng());
+        assert_eq!(proof.verify(g, h, &commitment), true);
+
+        let wrong_commitment = PedersenCommitment::commit(
+            g,
+            h,
+            <JubJub as ArkGroup>::ScalarField::rand(&mut rng),
+            opening.blinding,
+        );
+        assert_eq!(proof.verify(g, h, &wrong_commitment), false);
+    }
+}
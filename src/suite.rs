@@ -0,0 +1,71 @@
+//! Ciphersuites: pluggable (group, transcript hash) pairs.
+//!
+//! The protocol itself only ever needs two things from its environment: a
+//! [`CurveGroup`] to do arithmetic in, and a way to turn an ordered list of
+//! byte strings into a scalar of that group's scalar field. Bundling both
+//! into one [`Suite`] (mirroring FROST's `Ciphersuite`/`Group` split) lets
+//! callers swap in a SHA-256 or algebraic (e.g. Poseidon) transcript hash
+//! without touching the protocol code — which matters for in-circuit
+//! verification, where a sponge-friendly hash is mandatory.
+
+use ark_ec::CurveGroup;
+use ark_ed_on_bls12_381::EdwardsProjective as JubJub;
+use ark_ff::fields::PrimeField;
+use ark_std::vec::Vec;
+use sha3::{
+    digest::{ExtendableOutput, Update, XofReader},
+    Shake128,
+};
+
+/// A ciphersuite binds together the group the protocol runs in and the hash
+/// used to turn a transcript into pseudorandom output.
+pub trait Suite {
+    /// The group the protocol's commitments, ciphertexts and proofs live in.
+    type Group: CurveGroup;
+
+    /// Hash an ordered list of labelled byte strings into exactly `out_len`
+    /// pseudorandom bytes.
+    ///
+    /// This is the one primitive every suite must supply; [`hash_to_scalar`]
+    /// and [`crate::hash_to_curve`] are both built on top of it, so that
+    /// deriving a scalar and deriving a curve point (whose encoding is sized
+    /// by the *base* field, not the scalar field) never disagree about how
+    /// many bytes the hash owes them.
+    ///
+    /// [`hash_to_scalar`]: Suite::hash_to_scalar
+    fn hash_to_bytes(inputs: &[&[u8]], out_len: usize) -> Vec<u8>;
+
+    /// Hash an ordered list of labelled byte strings into a scalar of
+    /// `Self::Group`'s scalar field, via wide reduction: sample twice the
+    /// field's byte width from [`hash_to_bytes`](Suite::hash_to_bytes) so the
+    /// reduction mod the field order doesn't bias the result.
+    fn hash_to_scalar(inputs: &[&[u8]]) -> <Self::Group as CurveGroup>::ScalarField {
+        let modulus_bytes =
+            (<Self::Group as CurveGroup>::ScalarField::MODULUS_BIT_SIZE as usize + 7) / 8;
+        let bytes = Self::hash_to_bytes(inputs, 2 * modulus_bytes);
+        <Self::Group as CurveGroup>::ScalarField::from_be_bytes_mod_order(&bytes)
+    }
+}
+
+/// The default ciphersuite: JubJub with a `Shake128`-based transcript hash.
+///
+/// This keeps the group and the hash the crate used before ciphersuites were
+/// introduced and is the right choice unless you have a specific reason
+/// (e.g. an in-circuit verifier) to swap the hash or the group.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Shake128JubJub;
+
+impl Suite for Shake128JubJub {
+    type Group = JubJub;
+
+    fn hash_to_bytes(inputs: &[&[u8]], out_len: usize) -> Vec<u8> {
+        let mut hasher = Shake128::default();
+        for input in inputs {
+            hasher.update(input);
+        }
+
+        let mut out = ark_std::vec![0u8; out_len];
+        hasher.finalize_xof().read(&mut out);
+        out
+    }
+}
@@ -0,0 +1,75 @@
+//! A small domain-separated transcript for deriving Fiat–Shamir challenges.
+//!
+//! Every public value that is part of a statement (parameters, commitments,
+//! ciphertexts, prover messages) must be absorbed into the transcript in a
+//! fixed order before the challenge is squeezed out. Skipping a value here is
+//! what turns a proof into a "weak" Fiat–Shamir instantiation, since a
+//! malicious prover could otherwise grind over the omitted value or replay a
+//! proof against a different statement.
+//!
+//! The transcript itself is hash-agnostic: it only buffers labelled byte
+//! strings in order, and defers the actual hashing to a [`Suite`] so that
+//! callers can swap the transcript hash (e.g. for an in-circuit sponge)
+//! without touching any protocol code.
+
+use ark_serialize::CanonicalSerialize;
+use ark_std::vec::Vec;
+
+use crate::suite::Suite;
+
+/// An ordered, domain-separated absorb/squeeze transcript.
+///
+/// `label`s keep call sites self-documenting, and they are also absorbed
+/// alongside the bytes so that two differently-labelled but byte-identical
+/// inputs still produce distinct transcripts.
+///
+/// Every entry is absorbed length-prefixed (see [`Transcript::push`]), so
+/// the transcript is sound regardless of how a caller splits its inputs:
+/// without framing, `append(b"a", b"bc")` and `append(b"ab", b"c")` would
+/// hash identically once concatenated, letting an attacker shuffle bytes
+/// between entries without changing the challenge.
+pub struct Transcript {
+    entries: Vec<Vec<u8>>,
+}
+
+impl Transcript {
+    /// Start a new transcript for the given protocol domain separator.
+    pub fn new(domain_separator: &'static [u8]) -> Self {
+        let mut transcript = Transcript {
+            entries: Vec::new(),
+        };
+        transcript.push(domain_separator);
+        transcript
+    }
+
+    /// Absorb a labelled byte string into the transcript.
+    pub fn append(&mut self, label: &'static [u8], bytes: &[u8]) {
+        self.push(label);
+        self.push(bytes);
+    }
+
+    /// Absorb a labelled curve point, serialized in compressed form.
+    pub fn append_point<G: ark_ec::CurveGroup>(&mut self, label: &'static [u8], point: &G) {
+        let mut bytes = Vec::new();
+        point
+            .serialize_compressed(&mut bytes)
+            .expect("group element should serialize");
+        self.append(label, &bytes);
+    }
+
+    /// Squeeze the transcript into a challenge scalar using suite `S`'s
+    /// transcript hash.
+    pub fn challenge<S: Suite>(&self) -> <S::Group as ark_ec::CurveGroup>::ScalarField {
+        let refs: Vec<&[u8]> = self.entries.iter().map(Vec::as_slice).collect();
+        S::hash_to_scalar(&refs)
+    }
+
+    /// Absorb `bytes` prefixed with its own length as a fixed-width
+    /// big-endian `u64`, so the boundary between this entry and the next
+    /// can never be ambiguous no matter what either contains.
+    fn push(&mut self, bytes: &[u8]) {
+        let len_prefix = (bytes.len() as u64).to_be_bytes();
+        self.entries.push(Vec::from(len_prefix));
+        self.entries.push(Vec::from(bytes));
+    }
+}